@@ -0,0 +1,115 @@
+use crate::error::{HexCryptError, HexCryptResult};
+
+/// Magic bytes identifying a `hexcrypt` payload container.
+const MAGIC: &[u8; 4] = b"HEXC";
+/// Current container format version.
+const VERSION: u8 = 1;
+/// Size in bytes of the BLAKE3 digest stored in the header.
+const CHECKSUM_LEN: usize = 32;
+/// Size in bytes of the container header (`magic` + `version` + `length` + `checksum`).
+pub(crate) const HEADER_LEN: usize = MAGIC.len() + 1 + 8 + CHECKSUM_LEN;
+
+/// A parsed container header: the declared payload length and its expected BLAKE3 digest.
+pub(crate) struct Header {
+    /// Number of meaningful payload bytes following the header.
+    pub(crate) length: usize,
+    /// BLAKE3 digest the payload must hash to.
+    checksum: [u8; CHECKSUM_LEN],
+}
+
+/// Prepends a length- and checksum-prefixed container header to `payload`, so the exact number
+/// of meaningful bytes - and their integrity - survive the zero-padding `encrypt` applies to
+/// fill out the image.
+pub(crate) fn wrap(payload: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(HEADER_LEN + payload.len());
+    out.extend_from_slice(MAGIC);
+    out.push(VERSION);
+    out.extend_from_slice(&(payload.len() as u64).to_le_bytes());
+    out.extend_from_slice(blake3::hash(payload).as_bytes());
+    out.extend_from_slice(payload);
+
+    out
+}
+
+/// Parses a container header off the front of `buf`, without reading or verifying the payload.
+pub(crate) fn parse_header(buf: &[u8]) -> HexCryptResult<Header> {
+    if buf.len() < HEADER_LEN || &buf[..MAGIC.len()] != MAGIC {
+        return Err(HexCryptError::BadHeader("missing or invalid magic bytes".to_owned()));
+    }
+
+    let version = buf[MAGIC.len()];
+    if version != VERSION {
+        return Err(HexCryptError::BadHeader(format!("unsupported container version {version}")));
+    }
+
+    let len_start = MAGIC.len() + 1;
+    let len_bytes: [u8; 8] = buf[len_start..len_start + 8].try_into().unwrap();
+    let length = u64::from_le_bytes(len_bytes) as usize;
+
+    let checksum_start = len_start + 8;
+    let checksum: [u8; CHECKSUM_LEN] = buf[checksum_start..HEADER_LEN].try_into().unwrap();
+
+    Ok(Header { length, checksum })
+}
+
+/// Parses a container header off the front of `buf`, returns the exact payload bytes that
+/// follow it (ignoring any trailing zero-padding), and verifies them against the header's
+/// checksum.
+pub(crate) fn unwrap(buf: &[u8]) -> HexCryptResult<&[u8]> {
+    let header = parse_header(buf)?;
+
+    let payload_area = &buf[HEADER_LEN..];
+    if header.length > payload_area.len() {
+        return Err(HexCryptError::PayloadTooLarge(header.length, payload_area.len()));
+    }
+
+    let payload = &payload_area[..header.length];
+    verify_checksum(&header, payload)?;
+
+    Ok(payload)
+}
+
+/// Recomputes the BLAKE3 digest of `payload` and compares it against `header`'s, returning
+/// `HexCryptError::ChecksumMismatch` on divergence.
+pub(crate) fn verify_checksum(header: &Header, payload: &[u8]) -> HexCryptResult<()> {
+    let actual = *blake3::hash(payload).as_bytes();
+
+    if actual != header.checksum {
+        return Err(HexCryptError::ChecksumMismatch {
+            expected: hex(&header.checksum),
+            actual: hex(&actual),
+        });
+    }
+
+    Ok(())
+}
+
+/// Renders a byte slice as a lowercase hex string, for error messages.
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrap_unwrap_round_trip() {
+        let payload = b"the quick brown fox jumps over the lazy dog".to_vec();
+        let wrapped = wrap(&payload);
+
+        assert_eq!(unwrap(&wrapped).unwrap(), payload.as_slice());
+    }
+
+    #[test]
+    fn unwrap_detects_checksum_mismatch() {
+        let payload = b"hexcrypt".to_vec();
+        let mut wrapped = wrap(&payload);
+
+        // Flip every bit of the last payload byte without touching the stored checksum.
+        let last = wrapped.len() - 1;
+        wrapped[last] ^= 0xff;
+
+        assert!(matches!(unwrap(&wrapped), Err(HexCryptError::ChecksumMismatch { .. })));
+    }
+}
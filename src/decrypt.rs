@@ -1,19 +1,39 @@
 use std::{path::{Path, PathBuf}, fs::File, io::Write};
 
-use crate::error::HexCryptResult;
+use chacha20poly1305::{
+    aead::{Aead, KeyInit},
+    XChaCha20Poly1305, XNonce,
+};
 
-/// Decrypts the RGB image from the specified file and converts it back to UTF-8 encoded text.
+use crate::container;
+use crate::encrypt::{derive_key, SALT_LEN, NONCE_LEN};
+use crate::error::{HexCryptResult, HexCryptError};
+use crate::steg;
+
+/// Decrypts the RGB image from the specified file and writes the recovered bytes back to a file.
 ///
 /// # Arguments
 ///
 /// * `path` - A reference to a path that points to the image file to be decrypted.
-/// * `out_path` - An optional `PathBuf` representing the output path for the decrypted text.
-/// If `None`, `path` is used insted (with .txt extension)
+/// * `out_path` - An optional `PathBuf` representing the output path for the decrypted file.
+///   If `None`, `path` is used insted (with .txt extension)
+/// * `password` - An optional passphrase. Must match the one used to `encrypt` the image,
+///   otherwise `HexCryptError::DecryptionFailed` is returned.
+/// * `mnemonic` - An optional BIP39 mnemonic phrase, as an alternative to `password`. Must match
+///   the one used to `encrypt` the image. Ignored if `password` is set.
+///
+/// Images produced with a `--cover` are detected automatically: if the raw pixel bytes don't
+/// start with a valid container header, `decrypt` falls back to reading the header and payload
+/// out of the least-significant bits instead.
+///
+/// The recovered payload's BLAKE3 digest is checked against the one stored in the header, so
+/// corruption introduced by a lossy re-save or a manual edit surfaces as
+/// `HexCryptError::ChecksumMismatch` instead of silently-wrong output.
 ///
 /// # Errors
 ///
 /// This function can return a `HexCryptError` enum that represents different error cases, including I/O errors,
-/// issues related to image processing, and errors during the conversion of image bytes to text.
+/// issues related to image processing, and checksum or header validation failures.
 ///
 /// # Examples
 ///
@@ -24,7 +44,7 @@ use crate::error::HexCryptResult;
 /// let input_path = PathBuf::from("encrypted_image.png");
 /// let output_path = PathBuf::from("decrypted.txt");
 ///
-/// match decrypt(input_path, Some(output_path)) {
+/// match decrypt(input_path, Some(output_path), None, None) {
 ///     Ok(_) => println!("Decryption successful!"),
 ///     Err(e) => eprintln!("Error: {:?}", e),
 /// }
@@ -32,12 +52,26 @@ use crate::error::HexCryptResult;
 pub fn decrypt(
     path: impl AsRef<Path> + Clone,
     out_path: Option<PathBuf>,
+    password: Option<String>,
+    mnemonic: Option<String>,
 ) -> HexCryptResult<()> {
     let img = image::open(path.clone())?.into_rgb8();
-    let buf = img.as_raw().to_owned();
+    let raw = img.as_raw().to_owned();
+
+    let payload = match container::unwrap(&raw) {
+        Ok(payload) => payload.to_owned(),
+        Err(HexCryptError::BadHeader(_)) => decode_lsb(&raw)?,
+        Err(err) => return Err(err),
+    };
 
-    let text_nulled = String::from_utf8(buf)?;
-    let text = text_nulled.trim_matches(char::from(0));
+    let buf = if let Some(password) = password {
+        unseal_with_password(&payload, &password)?
+    } else if let Some(phrase) = mnemonic {
+        let key = crate::mnemonic::derive_key(&phrase)?;
+        unseal_with_key(&payload, &key)?
+    } else {
+        payload
+    };
 
     let out_path = match out_path {
         Some(path) => path,
@@ -45,7 +79,51 @@ pub fn decrypt(
     };
 
     let mut file = File::create(out_path)?;
-    file.write_all(text.as_bytes())?;
+    file.write_all(&buf)?;
 
     Ok(())
+}
+
+/// Reads a container header and payload out of the least-significant bits of `raw`, for images
+/// produced with `--cover`: the header is decoded first to learn the payload length, then that
+/// many more bytes are decoded to recover the payload itself.
+fn decode_lsb(raw: &[u8]) -> HexCryptResult<Vec<u8>> {
+    let header_bytes = steg::extract(raw, container::HEADER_LEN)?;
+    let header = container::parse_header(&header_bytes)?;
+
+    let mut full = steg::extract(raw, container::HEADER_LEN + header.length)?;
+    let payload = full.split_off(container::HEADER_LEN);
+
+    container::verify_checksum(&header, &payload)?;
+
+    Ok(payload)
+}
+
+/// Reverses [`seal_with_password`](crate::encrypt), reading `salt(16) || nonce(24) ||
+/// ciphertext` off the front of `buf`, re-deriving the key, and authenticating the ciphertext.
+pub(crate) fn unseal_with_password(buf: &[u8], password: &str) -> HexCryptResult<Vec<u8>> {
+    if buf.len() < SALT_LEN {
+        return Err(HexCryptError::DecryptionFailed);
+    }
+
+    let (salt, rest) = buf.split_at(SALT_LEN);
+    let key = derive_key(password, salt, HexCryptError::DecryptionFailed)?;
+
+    unseal_with_key(rest, &key)
+}
+
+/// Reverses [`seal_with_key`](crate::encrypt), reading `nonce(24) || ciphertext` off the front
+/// of `buf` and authenticating the ciphertext under the already-derived `key`.
+fn unseal_with_key(buf: &[u8], key: &[u8; 32]) -> HexCryptResult<Vec<u8>> {
+    if buf.len() < NONCE_LEN {
+        return Err(HexCryptError::DecryptionFailed);
+    }
+
+    let (nonce_bytes, ciphertext) = buf.split_at(NONCE_LEN);
+    let nonce = XNonce::from_slice(nonce_bytes);
+
+    let cipher = XChaCha20Poly1305::new(key.as_slice().into());
+    cipher
+        .decrypt(nonce, ciphertext)
+        .map_err(|_| HexCryptError::DecryptionFailed)
 }
\ No newline at end of file
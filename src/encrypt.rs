@@ -1,16 +1,43 @@
 use std::path::{Path, PathBuf};
 use image::RgbImage;
 
+use argon2::Argon2;
+use chacha20poly1305::{
+    aead::{Aead, KeyInit, OsRng},
+    XChaCha20Poly1305, XNonce,
+};
+use rand::RngCore;
+
+use crate::container;
 use crate::error::{HexCryptResult, HexCryptError};
+use crate::format::OutputFormat;
+use crate::steg;
+
+/// Length in bytes of the random Argon2id salt prepended to a keyed payload.
+pub(crate) const SALT_LEN: usize = 16;
+/// Length in bytes of the random XChaCha20-Poly1305 nonce prepended to a keyed payload.
+pub(crate) const NONCE_LEN: usize = 24;
 
-/// Encrypts the UTF-8 encoded text from the specified file and converts it into an RGB image.
+/// Encrypts the bytes of the specified file and converts them into an RGB image.
 ///
 /// # Arguments
 ///
-/// * `path` - A reference to a path that points to the file containing the text to be encrypted.
+/// * `path` - A reference to a path that points to the file to be encrypted. Its contents are
+///   read as raw bytes, so any file type is supported.
 /// * `size` - An optional `String` representing the custom size of the image (e.g., "16x32").
-/// * `out_path` - An optional `PathBuf` representing the output path for the generated image. 
-/// If `None`, `path` is used insted (with .png extension)
+/// * `out_path` - An optional `PathBuf` representing the output path for the generated image.
+///   If `None`, `path` is used insted (with .png extension)
+/// * `password` - An optional passphrase. If provided, the payload is sealed with
+///   XChaCha20-Poly1305 using a key derived from the passphrase via Argon2id, so it can only be
+///   recovered by someone who knows the passphrase.
+/// * `cover` - An optional path to an existing RGB image. If provided, the payload is hidden in
+///   its least-significant bits instead of a synthesized from-scratch image, so the output looks
+///   like an ordinary picture. `size` is ignored in this mode.
+/// * `format` - An optional output container override (e.g. `"png"`, `"webp"`), taking
+///   precedence over the extension of the output path. Lossy codecs are rejected.
+/// * `mnemonic` - An optional BIP39 mnemonic phrase, as an alternative to `password`. The key is
+///   derived deterministically from the phrase, so the same phrase always regenerates the same
+///   key and the image can be recovered from the words alone. Ignored if `password` is set.
 ///
 /// # Errors
 ///
@@ -27,7 +54,7 @@ use crate::error::{HexCryptResult, HexCryptError};
 /// let output_path = PathBuf::from("output.png");
 /// let size = Some("16x32".to_string());
 ///
-/// match encrypt(input_path, size, Some(output_path)) {
+/// match encrypt(input_path, size, Some(output_path), None, None, None, None) {
 ///     Ok(_) => println!("Encryption successful!"),
 ///     Err(e) => eprintln!("Error: {:?}", e),
 /// }
@@ -36,37 +63,68 @@ pub fn encrypt(
     path: impl AsRef<Path> + Clone,
     size: Option<String>,
     out_path: Option<PathBuf>,
+    password: Option<String>,
+    cover: Option<PathBuf>,
+    format: Option<String>,
+    mnemonic: Option<String>,
 ) -> HexCryptResult<()> {
-    let text = std::fs::read_to_string(path.clone())?;
-    let mut buf = text.as_bytes().to_owned();
-    
-    let size = match size {
-        Some(s) => parse_size(&s)?,
-        None => {
-            let n = ((buf.len() / 3) as f32).sqrt().ceil() as u32;
-            (n, n)
-        },
+    let mut buf = std::fs::read(path.clone())?;
+
+    if let Some(password) = password {
+        buf = seal_with_password(&buf, &password)?;
+    } else if let Some(phrase) = mnemonic {
+        let key = crate::mnemonic::derive_key(&phrase)?;
+        buf = seal_with_key(&buf, &key)?;
+    }
+
+    let buf = container::wrap(&buf);
+
+    let output_format = match (&out_path, &format) {
+        (Some(out_path), _) => OutputFormat::resolve(out_path, format.as_deref())?,
+        (None, Some(format)) => OutputFormat::resolve(Path::new(""), Some(format))?,
+        (None, None) => OutputFormat::Png,
     };
 
-    let diff = (size.0 * size.1) as i32 - (buf.len() / 3) as i32;
+    let image_path = match out_path {
+        Some(path) => path,
+        None => PathBuf::from(format!(
+            "{}.{}",
+            path.as_ref().file_stem().expect("Cannot extract file path").to_str().unwrap(),
+            output_format.extension(),
+        )),
+    };
 
-    match diff {
-        0 => {},
-        1.. => {
-            for _ in 0..diff {
-                buf.extend(&[0, 0, 0]);
-            }
+    let image = match cover {
+        Some(cover_path) => {
+            let cover_image = image::open(cover_path)?.into_rgb8();
+            steg::embed(cover_image, &buf)?
         },
-        _ => return Err(HexCryptError::CannotCreateImage(size.0, size.1)),
-    }
+        None => {
+            let mut buf = buf;
 
-    let image_path = match out_path {
-        Some(path) => path.to_str().unwrap().to_owned(),
-        None => format!("{}.png", path.as_ref().file_stem().expect("Cannot extract file path").to_str().unwrap()),
+            let size = match size {
+                Some(s) => parse_size(&s)?,
+                None => {
+                    let n = (buf.len() as f32 / 3.0).ceil().sqrt().ceil() as u32;
+                    (n, n)
+                },
+            };
+
+            // Pad in bytes, not whole pixels: `buf.len()` is rarely a multiple of 3 once a
+            // header or AEAD tag is mixed in, and a short last pixel panics every encoder.
+            let diff = (size.0 as i64 * size.1 as i64 * 3) - buf.len() as i64;
+
+            match diff {
+                0 => {},
+                1.. => buf.resize(buf.len() + diff as usize, 0),
+                _ => return Err(HexCryptError::CannotCreateImage(size.0, size.1)),
+            }
+
+            RgbImage::from_raw(size.0, size.1, buf).ok_or(HexCryptError::CannotCreateImage(size.0, size.1))?
+        },
     };
-    
-    let image = RgbImage::from_raw(size.0, size.1, buf).ok_or(HexCryptError::CannotCreateImage(size.0, size.1))?;
-    image.save(image_path)?;
+
+    output_format.save(&image, &image_path)?;
 
     Ok(())
 }
@@ -98,4 +156,75 @@ fn parse_size(s: &str) -> HexCryptResult<(u32, u32)> {
     let h = h.parse::<u32>().map_err(|_| HexCryptError::InvalidImageSize(s.to_owned()))?;
 
     Ok((w, h))
+}
+
+/// Derives a 32-byte XChaCha20-Poly1305 key from a passphrase and salt using Argon2id. `on_err`
+/// is returned if Argon2id itself fails (e.g. absurd parameters), so the caller can report it as
+/// an encryption or a decryption failure as appropriate, instead of always assuming decryption.
+pub(crate) fn derive_key(password: &str, salt: &[u8], on_err: HexCryptError) -> HexCryptResult<[u8; 32]> {
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(password.as_bytes(), salt, &mut key)
+        .map_err(|_| on_err)?;
+
+    Ok(key)
+}
+
+/// Seals `plaintext` under a passphrase, producing `salt(16) || nonce(24) || ciphertext`, where
+/// the ciphertext carries its Poly1305 tag in its final 16 bytes.
+fn seal_with_password(plaintext: &[u8], password: &str) -> HexCryptResult<Vec<u8>> {
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+
+    let key = derive_key(password, &salt, HexCryptError::EncryptionFailed)?;
+    let sealed = seal_with_key(plaintext, &key)?;
+
+    let mut out = Vec::with_capacity(SALT_LEN + sealed.len());
+    out.extend_from_slice(&salt);
+    out.extend_from_slice(&sealed);
+
+    Ok(out)
+}
+
+/// Seals `plaintext` under an already-derived key, producing `nonce(24) || ciphertext`, where
+/// the ciphertext carries its Poly1305 tag in its final 16 bytes.
+pub(crate) fn seal_with_key(plaintext: &[u8], key: &[u8; 32]) -> HexCryptResult<Vec<u8>> {
+    let mut nonce_bytes = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce_bytes);
+    let nonce = XNonce::from_slice(&nonce_bytes);
+
+    let cipher = XChaCha20Poly1305::new(key.as_slice().into());
+    let ciphertext = cipher
+        .encrypt(nonce, plaintext)
+        .map_err(|_| HexCryptError::EncryptionFailed)?;
+
+    let mut out = Vec::with_capacity(NONCE_LEN + ciphertext.len());
+    out.extend_from_slice(&nonce_bytes);
+    out.extend_from_slice(&ciphertext);
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decrypt::unseal_with_password;
+
+    #[test]
+    fn seal_unseal_round_trip() {
+        let plaintext = b"the quick brown fox jumps over the lazy dog".to_vec();
+        let sealed = seal_with_password(&plaintext, "correct horse battery staple").unwrap();
+
+        let unsealed = unseal_with_password(&sealed, "correct horse battery staple").unwrap();
+        assert_eq!(unsealed, plaintext);
+    }
+
+    #[test]
+    fn unseal_rejects_wrong_password() {
+        let plaintext = b"hexcrypt".to_vec();
+        let sealed = seal_with_password(&plaintext, "correct horse battery staple").unwrap();
+
+        let err = unseal_with_password(&sealed, "wrong password").unwrap_err();
+        assert!(matches!(err, HexCryptError::DecryptionFailed));
+    }
 }
\ No newline at end of file
@@ -1,5 +1,4 @@
 use std::io;
-use std::string::FromUtf8Error;
 
 use image::ImageError;
 use thiserror::Error;
@@ -23,9 +22,50 @@ pub enum HexCryptError {
     #[error("Error processing image")]
     ImageError(#[from] ImageError),
 
-    /// Represents an error that occurs when image bytes cannot be converted to a string.
-    #[error("Cannot convert image bytes to string")]
-    BytesToString(#[from] FromUtf8Error),
+    /// Represents an error that occurs when a passphrase fails to authenticate the payload,
+    /// either because it is wrong or because the image has been tampered with.
+    #[error("Decryption failed: wrong password or corrupted image")]
+    DecryptionFailed,
+
+    /// Represents an error that occurs when sealing the payload with XChaCha20-Poly1305 fails
+    /// during encryption, e.g. key derivation returning an invalid-length key.
+    #[error("Encryption failed")]
+    EncryptionFailed,
+
+    /// Represents an error that occurs when the container header is missing, has the wrong
+    /// magic bytes, or declares an unsupported version.
+    #[error("Invalid container header: {0}")]
+    BadHeader(String),
+
+    /// Represents an error that occurs when the header declares a payload length larger than
+    /// the number of bytes actually available to read it from.
+    #[error("Payload length {0} exceeds the {1} bytes available in the image")]
+    PayloadTooLarge(usize, usize),
+
+    /// Represents an error that occurs when a cover image does not have enough channel bytes
+    /// to hold the payload, one bit per byte.
+    #[error("Cover image is too small: need {0} bits but only {1} are available")]
+    CoverTooSmall(usize, usize),
+
+    /// Represents an error that occurs when the requested output container is a lossy codec
+    /// (or unrecognized), which would corrupt the payload on save.
+    #[error("`{0}` is a lossy or unsupported format; use png, bmp, tiff, or webp")]
+    LossyFormat(String),
+
+    /// Represents an error that occurs when the recovered payload's BLAKE3 digest doesn't match
+    /// the one stored in the header, meaning the image was corrupted or tampered with.
+    #[error("Checksum mismatch: expected {expected}, got {actual}")]
+    ChecksumMismatch {
+        /// The digest recorded in the container header.
+        expected: String,
+        /// The digest actually recomputed over the recovered payload.
+        actual: String,
+    },
+
+    /// Represents an error that occurs when a `--mnemonic` phrase is not valid BIP39: wrong
+    /// word count, a word outside the wordlist, or a bad checksum.
+    #[error("Invalid BIP39 mnemonic phrase")]
+    InvalidMnemonic,
 }
 
 /// Alias for a `Result` that uses the `HexCryptError` enum as the error type.
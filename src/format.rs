@@ -0,0 +1,100 @@
+use std::{fs::File, path::Path};
+
+use image::{
+    codecs::{bmp::BmpEncoder, png::PngEncoder, tiff::TiffEncoder, webp::WebPEncoder},
+    ColorType, ImageEncoder, RgbImage,
+};
+
+use crate::error::{HexCryptError, HexCryptResult};
+
+/// Lossless image container `hexcrypt` is allowed to write a payload into. Lossy codecs (JPEG
+/// and friends) destroy the bit-exact pixel values a payload relies on, so they're rejected
+/// rather than silently corrupting the output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum OutputFormat {
+    /// Portable Network Graphics.
+    Png,
+    /// Windows Bitmap.
+    Bmp,
+    /// Tagged Image File Format.
+    Tiff,
+    /// WebP, always encoded with the lossless codec path.
+    WebP,
+}
+
+impl OutputFormat {
+    /// Resolves the format to use for `path`, preferring an explicit `--format` override over
+    /// the file extension.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HexCryptError::LossyFormat` for JPEG, GIF, AVIF, or any other unrecognized or
+    /// inherently lossy codec.
+    pub(crate) fn resolve(path: &Path, format: Option<&str>) -> HexCryptResult<Self> {
+        let name = match format {
+            Some(format) => format.to_owned(),
+            None => path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .unwrap_or_default()
+                .to_owned(),
+        };
+
+        match name.to_lowercase().as_str() {
+            "png" => Ok(Self::Png),
+            "bmp" => Ok(Self::Bmp),
+            "tiff" | "tif" => Ok(Self::Tiff),
+            "webp" => Ok(Self::WebP),
+            _ => Err(HexCryptError::LossyFormat(name)),
+        }
+    }
+
+    /// The file extension this format is conventionally saved with.
+    pub(crate) fn extension(self) -> &'static str {
+        match self {
+            Self::Png => "png",
+            Self::Bmp => "bmp",
+            Self::Tiff => "tiff",
+            Self::WebP => "webp",
+        }
+    }
+
+    /// Encodes `image` into `path` using this format.
+    pub(crate) fn save(self, image: &RgbImage, path: &Path) -> HexCryptResult<()> {
+        let mut file = File::create(path)?;
+        let (width, height) = image.dimensions();
+
+        match self {
+            Self::Png => PngEncoder::new(&mut file).write_image(image, width, height, ColorType::Rgb8)?,
+            Self::Bmp => BmpEncoder::new(&mut file).write_image(image, width, height, ColorType::Rgb8)?,
+            Self::Tiff => TiffEncoder::new(&mut file).write_image(image, width, height, ColorType::Rgb8)?,
+            Self::WebP => WebPEncoder::new_lossless(&mut file).write_image(image, width, height, ColorType::Rgb8)?,
+        };
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_accepts_lossless_formats() {
+        assert_eq!(OutputFormat::resolve(Path::new("out.png"), None).unwrap(), OutputFormat::Png);
+        assert_eq!(OutputFormat::resolve(Path::new("out.bmp"), None).unwrap(), OutputFormat::Bmp);
+        assert_eq!(OutputFormat::resolve(Path::new("out.tiff"), None).unwrap(), OutputFormat::Tiff);
+        assert_eq!(OutputFormat::resolve(Path::new("out.webp"), None).unwrap(), OutputFormat::WebP);
+    }
+
+    #[test]
+    fn resolve_rejects_lossy_format() {
+        let err = OutputFormat::resolve(Path::new("out.jpg"), None).unwrap_err();
+        assert!(matches!(err, HexCryptError::LossyFormat(_)));
+    }
+
+    #[test]
+    fn resolve_prefers_explicit_format_over_extension() {
+        assert_eq!(OutputFormat::resolve(Path::new("out.png"), Some("bmp")).unwrap(), OutputFormat::Bmp);
+    }
+}
@@ -1,4 +1,4 @@
-//! `hexcrypt` is a CLI application to convert UTF-8 encoded text into RGB images.
+//! `hexcrypt` is a CLI application to convert arbitrary files into RGB images.
 
 #![warn(missing_docs)]
 #![warn(clippy::missing_docs_in_private_items)]
@@ -9,41 +9,74 @@ use clap::Parser;
 use decrypt::*;
 use encrypt::*;
 
-/// The `decrypt` module contains functionality related to decrypting hex-encrypted images to text.
+/// The `container` module contains the length-prefixed payload header shared by `encrypt` and `decrypt`.
+mod container;
+/// The `decrypt` module contains functionality related to decrypting hex-encrypted images back to files.
 mod decrypt;
-/// The `encrypt` module contains functionality related to encrypting text to RGB images.
+/// The `encrypt` module contains functionality related to encrypting files to RGB images.
 mod encrypt;
 /// The `error` module contains custom error types and error handling functionality.
 mod error;
+/// The `format` module contains the lossless output format whitelist and its encoders.
+mod format;
+/// The `mnemonic` module contains BIP39 mnemonic key derivation and generation.
+mod mnemonic;
+/// The `steg` module contains LSB steganography helpers for embedding a payload into a cover image.
+mod steg;
 
 /// The `Args` struct represents the command-line arguments for the `hexcrypt` application.
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
-    /// Path to the text file to be encrypted.
-    #[arg(short, long, required = true, conflicts_with = "decrypt")]
+    /// Path to the file to be encrypted.
+    #[arg(short, long, conflicts_with = "decrypt", required_unless_present_any = ["decrypt", "gen_mnemonic"])]
     encrypt: Option<PathBuf>,
     /// Path to the image to be decrypted.
-    #[arg(short, long, required = true, conflicts_with = "encrypt")]
+    #[arg(short, long, conflicts_with = "encrypt", required_unless_present_any = ["encrypt", "gen_mnemonic"])]
     decrypt: Option<PathBuf>,
     /// Path to the output file (optional)
     #[arg(short, long)]
     output: Option<PathBuf>,
-    /// Whether use custom size of an image. E.g. `-s 16x32`
+    /// Whether use custom size of an image. E.g. `-s 16x32`. Ignored when `--cover` is given.
     #[arg(short, long, conflicts_with = "decrypt")]
     size: Option<String>,
+    /// Passphrase used to seal (or unseal) the payload with XChaCha20-Poly1305. The key is
+    /// derived from it via Argon2id, so the same passphrase must be given to `--decrypt`.
+    #[arg(short, long, conflicts_with = "mnemonic")]
+    password: Option<String>,
+    /// Path to an existing RGB image to hide the payload inside via LSB steganography, instead
+    /// of synthesizing a from-scratch noise image. Only valid with `--encrypt`.
+    #[arg(short, long, conflicts_with = "decrypt")]
+    cover: Option<PathBuf>,
+    /// Output container format (png, bmp, tiff, webp), overriding the one inferred from the
+    /// output file extension. Lossy formats like jpg are always rejected.
+    #[arg(short, long, conflicts_with = "decrypt")]
+    format: Option<String>,
+    /// BIP39 mnemonic phrase used to derive the XChaCha20-Poly1305 key deterministically,
+    /// instead of a `--password`. The same phrase must be given to `--decrypt`.
+    #[arg(short, long, conflicts_with = "password")]
+    mnemonic: Option<String>,
+    /// Generate a fresh 24-word BIP39 mnemonic phrase from OS entropy and print it, ignoring
+    /// every other argument.
+    #[arg(short, long)]
+    gen_mnemonic: bool,
 }
 
 /// The main function of the `hexcrypt` application.
 fn main() -> Result<()> {
     // Parse command-line arguments.
     let args = Args::parse();
-    
+
+    if args.gen_mnemonic {
+        println!("{}", mnemonic::generate());
+        return Ok(());
+    }
+
     // Encrypt or decrypt image based on the provided arguments.
     if let Some(path) = args.encrypt {
-        encrypt(path, args.size, args.output)?;
+        encrypt(path, args.size, args.output, args.password, args.cover, args.format, args.mnemonic)?;
     } else if let Some(path) = args.decrypt {
-        decrypt(path, args.output)?;
+        decrypt(path, args.output, args.password, args.mnemonic)?;
     }
 
     Ok(())
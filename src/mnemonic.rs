@@ -0,0 +1,43 @@
+use bip39::Mnemonic;
+
+use crate::error::{HexCryptError, HexCryptResult};
+
+/// Derives a 32-byte XChaCha20-Poly1305 key from a BIP39 mnemonic phrase.
+///
+/// The phrase is validated against the BIP39 English wordlist and checksum, then run through
+/// the standard PBKDF2-HMAC-SHA512 (2048 iterations, salt `"mnemonic"`) to produce a 64-byte
+/// seed; the first 32 bytes become the key. The same phrase always reproduces the same key.
+pub(crate) fn derive_key(phrase: &str) -> HexCryptResult<[u8; 32]> {
+    let mnemonic = Mnemonic::parse(phrase).map_err(|_| HexCryptError::InvalidMnemonic)?;
+    let seed = mnemonic.to_seed("");
+
+    let mut key = [0u8; 32];
+    key.copy_from_slice(&seed[..32]);
+
+    Ok(key)
+}
+
+/// Samples OS entropy into a fresh 24-word BIP39 mnemonic phrase.
+pub(crate) fn generate() -> String {
+    Mnemonic::generate(24)
+        .expect("24 is a valid BIP39 word count")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derive_key_is_deterministic_for_a_valid_phrase() {
+        let phrase = generate();
+
+        assert_eq!(derive_key(&phrase).unwrap(), derive_key(&phrase).unwrap());
+    }
+
+    #[test]
+    fn derive_key_rejects_invalid_phrase() {
+        let err = derive_key("not a valid bip39 mnemonic phrase at all").unwrap_err();
+        assert!(matches!(err, HexCryptError::InvalidMnemonic));
+    }
+}
@@ -0,0 +1,56 @@
+use image::RgbImage;
+
+use crate::error::{HexCryptError, HexCryptResult};
+
+/// Embeds `data` into the least-significant bit of each channel byte of `cover`, overwriting
+/// pixels in raster order (R, G, B per pixel) until every bit has been written.
+pub(crate) fn embed(cover: RgbImage, data: &[u8]) -> HexCryptResult<RgbImage> {
+    let (width, height) = cover.dimensions();
+    let mut raw = cover.into_raw();
+
+    let capacity = raw.len();
+    let needed = data.len() * 8;
+
+    if capacity < needed {
+        return Err(HexCryptError::CoverTooSmall(needed, capacity));
+    }
+
+    for (i, channel) in raw.iter_mut().take(needed).enumerate() {
+        let bit = (data[i / 8] >> (7 - i % 8)) & 1;
+        *channel = (*channel & !1) | bit;
+    }
+
+    Ok(RgbImage::from_raw(width, height, raw).expect("dimensions are unchanged"))
+}
+
+/// Reads `num_bytes` worth of bits back out of the least-significant bit of each channel byte
+/// in `raw`, in the same raster order `embed` wrote them in.
+pub(crate) fn extract(raw: &[u8], num_bytes: usize) -> HexCryptResult<Vec<u8>> {
+    let needed = num_bytes * 8;
+    if raw.len() < needed {
+        return Err(HexCryptError::CoverTooSmall(needed, raw.len()));
+    }
+
+    let mut out = vec![0u8; num_bytes];
+    for (i, channel) in raw.iter().take(needed).enumerate() {
+        out[i / 8] |= (channel & 1) << (7 - i % 8);
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn embed_extract_round_trip() {
+        let cover = RgbImage::from_raw(8, 8, vec![0xff; 8 * 8 * 3]).unwrap();
+        let data = b"hexcrypt".to_vec();
+
+        let embedded = embed(cover, &data).unwrap();
+        let extracted = extract(embedded.as_raw(), data.len()).unwrap();
+
+        assert_eq!(extracted, data);
+    }
+}